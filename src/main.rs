@@ -1,34 +1,117 @@
 // SPDX-License-Identifier: WTFPL
-//! A cross-platform utility to extract system include directories from C++ compilers.
+//! A cross-platform utility to extract system include and library directories from C++ compilers.
 //!
-//! This tool queries a C++ compiler to discover its default system include directories.
-//! It supports gcc-like compilers (gcc, clang, etc.) and provides platform-specific fallbacks:
+//! This tool queries a C++ compiler to discover its default system include directories and,
+//! via `--kind library`/`--kind both`, its library search directories. It supports gcc-like
+//! compilers (gcc, clang, etc.) and provides platform-specific fallbacks:
 //!
 //! - **Unix-like platforms**: Uses `/usr/bin/c++` as the default compiler when none is specified
-//! - **Windows**: Parses the `INCLUDE` environment variable (`;` separated paths) when no compiler is specified
+//! - **Windows**: Parses the `INCLUDE`/`LIB` environment variables (`;` separated paths) when no compiler is specified
 //!
-//! For gcc-like compilers, the tool invokes the compiler with `-v -E -x c++ -` and parses
-//! the output to extract include directory paths.
+//! For gcc-like compilers, the tool invokes the compiler with `-v -E -x <lang> -` (language
+//! selectable via `--lang`, standard via `--std`) to extract include directories, and with
+//! `-print-search-dirs` to extract library directories. The compiler itself is resolved from
+//! `--compiler`, then the `CC`/`CXX` environment variable, then (with `--target`) a
+//! cross-prefixed or `--target=`-qualified compiler, falling back to the platform default.
+//! `CFLAGS`/`CXXFLAGS` are forwarded when querying include directories; the
+//! `-print-search-dirs` invocation does not receive them.
 
 use clap::Parser;
 use regex::Regex;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
+/// Which class of compiler search directories to report.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    /// Only the `#include <...>` system include directories.
+    Include,
+    /// Only the compiler's library search directories.
+    Library,
+    /// Both include and library search directories.
+    Both,
+}
+
+/// Source language to request from the compiler via `-x`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Lang {
+    C,
+    #[value(name = "c++")]
+    Cpp,
+    Objc,
+    #[value(name = "objc++")]
+    ObjCpp,
+}
+
+impl Lang {
+    /// The `-x` language name this variant maps to.
+    fn as_compiler_lang(self) -> &'static str {
+        match self {
+            Lang::C => "c",
+            Lang::Cpp => "c++",
+            Lang::Objc => "objective-c",
+            Lang::ObjCpp => "objective-c++",
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(name = "get-system-include-dirs")]
-#[command(about = "Extract system include directories from C++ compiler", long_about = None)]
+#[command(about = "Extract system include and library search directories from a C++ compiler", long_about = None)]
 struct Args {
     /// Path to the C++ compiler to query
     #[arg(short, long)]
     compiler: Option<PathBuf>,
+
+    /// Which kind of search directories to extract
+    #[arg(long, value_enum, default_value_t = Kind::Include)]
+    kind: Kind,
+
+    /// Source language to pass to the compiler via `-x`
+    #[arg(long, value_enum, default_value_t = Lang::Cpp)]
+    lang: Lang,
+
+    /// Language standard forwarded to the compiler (e.g. c++17, gnu11)
+    #[arg(long)]
+    std: Option<String>,
+
+    /// Target triple for cross-compilation (e.g. aarch64-linux-gnu)
+    #[arg(long)]
+    target: Option<String>,
+
+    /// Extra compiler-style flags to scan for `-L`/`-Ldir` library search directories.
+    /// Must come after a `--` separator (e.g. `-- -L/opt/lib`), so flags like `--target`
+    /// can appear in any order without being swallowed by this trailing list.
+    #[arg(last = true)]
+    extra_args: Vec<String>,
 }
 
 fn main() {
     let args = Args::parse();
 
-    match get_include_dirs(args.compiler) {
+    let result = match args.kind {
+        Kind::Include => get_include_dirs(args.compiler, args.lang, args.std.as_deref(), args.target.as_deref()),
+        Kind::Library => get_library_dirs(args.compiler, args.lang, args.target.as_deref(), &args.extra_args),
+        Kind::Both => {
+            let includes = get_include_dirs(
+                args.compiler.clone(),
+                args.lang,
+                args.std.as_deref(),
+                args.target.as_deref(),
+            );
+            let libraries = get_library_dirs(args.compiler, args.lang, args.target.as_deref(), &args.extra_args);
+            match (includes, libraries) {
+                (Ok(mut dirs), Ok(lib_dirs)) => {
+                    dirs.extend(lib_dirs);
+                    Ok(dirs)
+                }
+                (Err(e), _) | (_, Err(e)) => Err(e),
+            }
+        }
+    };
+
+    match result {
         Ok(dirs) => {
             for dir in dirs {
                 println!("{}", dir);
@@ -45,7 +128,11 @@ fn main() {
 ///
 /// # Arguments
 ///
-/// * `compiler` - Optional path to a C++ compiler. If `None`, uses platform-specific defaults.
+/// * `compiler` - Optional path to a C++ compiler. If `None`, falls back to the `CC`/`CXX`
+///   environment variable (chosen by `lang`), then to a cross-prefixed or platform default.
+/// * `lang` - Source language to request via `-x`.
+/// * `std` - Optional language standard forwarded to the compiler via `-std=`.
+/// * `target` - Optional target triple for cross-compilation.
 ///
 /// # Returns
 ///
@@ -54,30 +141,93 @@ fn main() {
 ///
 /// # Platform behavior
 ///
-/// - **Windows (no compiler specified)**: Parses the `INCLUDE` environment variable
-/// - **Unix-like (no compiler specified)**: Uses `/usr/bin/c++`
-/// - **Compiler specified**: Invokes the compiler with `-v` to extract include directories
-fn get_include_dirs(compiler: Option<PathBuf>) -> Result<Vec<String>, String> {
-    if cfg!(windows) && compiler.is_none() {
-        // On Windows without a specified compiler, parse $INCLUDE
+/// - **Windows (no compiler, env var, or target given)**: Parses the `INCLUDE` environment variable
+/// - **Unix-like (no compiler, env var, or target given)**: Uses `/usr/bin/c++`
+/// - **Compiler resolved**: Invokes the compiler with `-v` to extract include directories
+fn get_include_dirs(
+    compiler: Option<PathBuf>,
+    lang: Lang,
+    std: Option<&str>,
+    target: Option<&str>,
+) -> Result<Vec<String>, String> {
+    if cfg!(windows) && !has_explicit_compiler(&compiler, lang, target) {
+        // On Windows without a specified compiler, env var, or target, parse $INCLUDE
         return get_windows_include_dirs();
     }
 
-    // Unix-like platforms or when compiler is specified
-    let compiler_path = compiler.unwrap_or_else(|| {
-        if cfg!(unix) {
-            PathBuf::from("/usr/bin/c++")
-        } else {
-            PathBuf::from("c++")
-        }
-    });
+    let compiler_path = resolve_compiler(compiler, lang, target);
 
     // On Windows, check if the compiler is MSVC-like
     if cfg!(windows) && is_msvc_like_compiler(&compiler_path) {
         return get_windows_include_dirs();
     }
 
-    get_compiler_include_dirs(&compiler_path)
+    get_compiler_include_dirs(&compiler_path, lang, std, target)
+}
+
+/// Resolves which compiler to invoke, honoring (in order) an explicit path, the `CC`/`CXX`
+/// environment variable for `lang`, a target-prefixed cross compiler, and finally the
+/// platform default.
+///
+/// # Arguments
+///
+/// * `compiler` - Explicit compiler path, if given on the command line.
+/// * `lang` - Source language, used to pick between `CC` and `CXX`.
+/// * `target` - Optional target triple for cross-compilation.
+fn resolve_compiler(compiler: Option<PathBuf>, lang: Lang, target: Option<&str>) -> PathBuf {
+    if let Some(compiler) = compiler {
+        return compiler;
+    }
+
+    if let Ok(from_env) = env::var(compiler_env_var(lang)) {
+        if !from_env.is_empty() {
+            return PathBuf::from(from_env);
+        }
+    }
+
+    if let Some(target) = target {
+        let tool = match lang {
+            Lang::C | Lang::Objc => "gcc",
+            Lang::Cpp | Lang::ObjCpp => "g++",
+        };
+        return PathBuf::from(format!("{}-{}", target, tool));
+    }
+
+    if cfg!(unix) {
+        PathBuf::from("/usr/bin/c++")
+    } else {
+        PathBuf::from("c++")
+    }
+}
+
+/// Whether a compiler was resolved from something other than the platform default: an
+/// explicit `--compiler` path, a non-empty `CC`/`CXX` environment variable, or `--target`.
+fn has_explicit_compiler(compiler: &Option<PathBuf>, lang: Lang, target: Option<&str>) -> bool {
+    compiler.is_some() || env::var(compiler_env_var(lang)).is_ok_and(|v| !v.is_empty()) || target.is_some()
+}
+
+/// The environment variable used to select a compiler for `lang` (`CC` or `CXX`).
+fn compiler_env_var(lang: Lang) -> &'static str {
+    match lang {
+        Lang::C | Lang::Objc => "CC",
+        Lang::Cpp | Lang::ObjCpp => "CXX",
+    }
+}
+
+/// The environment variable used to forward extra flags for `lang` (`CFLAGS` or `CXXFLAGS`).
+fn compiler_flags_env_var(lang: Lang) -> &'static str {
+    match lang {
+        Lang::C | Lang::Objc => "CFLAGS",
+        Lang::Cpp | Lang::ObjCpp => "CXXFLAGS",
+    }
+}
+
+/// Checks if a compiler is Clang-like based on its filename (e.g. `clang`, `clang++`).
+fn is_clang_like_compiler(compiler: &Path) -> bool {
+    compiler
+        .file_name()
+        .and_then(|f| f.to_str())
+        .is_some_and(|name| name.contains("clang"))
 }
 
 /// Checks if a compiler is MSVC-like based on its filename.
@@ -101,15 +251,17 @@ fn is_msvc_like_compiler(compiler: &PathBuf) -> bool {
     false
 }
 
-/// Extracts include directories from the Windows `INCLUDE` environment variable.
+/// Extracts include directories from the Windows `INCLUDE` environment variable, falling
+/// back to MSVC discovery when it is not set.
 ///
-/// Parses semicolon-separated paths from the `INCLUDE` environment variable,
-/// filtering out empty entries.
+/// Parses semicolon-separated paths from the `INCLUDE` environment variable, filtering out
+/// empty entries. If `INCLUDE` is unset (i.e. we are not running inside a Developer Command
+/// Prompt), locates a Visual Studio installation and its matching Windows SDK instead.
 ///
 /// # Returns
 ///
 /// * `Ok(Vec<String>)` - A vector of include directory paths
-/// * `Err(String)` - An error if the `INCLUDE` environment variable is not set
+/// * `Err(String)` - An error if `INCLUDE` is unset and MSVC discovery also fails
 fn get_windows_include_dirs() -> Result<Vec<String>, String> {
     match env::var("INCLUDE") {
         Ok(include_var) => {
@@ -120,32 +272,232 @@ fn get_windows_include_dirs() -> Result<Vec<String>, String> {
                 .collect();
             Ok(dirs)
         }
-        Err(_) => Err("INCLUDE environment variable not set".to_string()),
+        Err(_) => discover_msvc_include_dirs(),
+    }
+}
+
+/// Discovers MSVC include directories without relying on a pre-populated `INCLUDE`.
+///
+/// Locates a Visual Studio installation the way `cc`/`cargo` do: queries `vswhere.exe`
+/// (under `%ProgramFiles(x86)%\Microsoft Visual Studio\Installer`) for the latest
+/// installation path, then combines the VC tools include directory
+/// (`VC\Tools\MSVC\<version>\include`) with the matching Windows SDK include subfolders
+/// (`ucrt`, `shared`, `um`, `winrt`) resolved from the registry-recorded SDK root.
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - The discovered include directories
+/// * `Err(String)` - An error if no Visual Studio installation or SDK could be located
+fn discover_msvc_include_dirs() -> Result<Vec<String>, String> {
+    let vs_install_path = find_vs_install_path()?;
+
+    let mut dirs = vec![find_vc_tools_include_dir(&vs_install_path)?];
+    dirs.extend(find_windows_sdk_include_dirs()?);
+
+    Ok(dirs)
+}
+
+/// Queries `vswhere.exe` for the latest Visual Studio installation with the VC++ toolset.
+///
+/// # Returns
+///
+/// * `Ok(PathBuf)` - The Visual Studio installation root
+/// * `Err(String)` - An error if `vswhere.exe` could not be run or found no installation
+fn find_vs_install_path() -> Result<PathBuf, String> {
+    let program_files_x86 = env::var("ProgramFiles(x86)")
+        .map_err(|_| "ProgramFiles(x86) environment variable not set".to_string())?;
+    let vswhere = PathBuf::from(program_files_x86)
+        .join("Microsoft Visual Studio")
+        .join("Installer")
+        .join("vswhere.exe");
+
+    let output = Command::new(&vswhere)
+        .args([
+            "-latest",
+            "-products",
+            "*",
+            "-requires",
+            "Microsoft.VisualStudio.Component.VC.Tools.x86.x64",
+            "-property",
+            "installationPath",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute {}: {}", vswhere.display(), e))?;
+
+    let install_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if install_path.is_empty() {
+        return Err("No Visual Studio installation found via vswhere".to_string());
+    }
+
+    Ok(PathBuf::from(install_path))
+}
+
+/// Finds the newest `VC\Tools\MSVC\<version>\include` directory under a VS installation.
+///
+/// # Arguments
+///
+/// * `vs_install_path` - The Visual Studio installation root, as reported by `vswhere.exe`
+///
+/// # Returns
+///
+/// * `Ok(String)` - The VC tools include directory
+/// * `Err(String)` - An error if no MSVC toolset version could be found
+fn find_vc_tools_include_dir(vs_install_path: &Path) -> Result<String, String> {
+    let msvc_root = vs_install_path.join("VC").join("Tools").join("MSVC");
+    let latest = latest_subdirectory(&msvc_root)?;
+
+    Ok(latest.join("include").to_string_lossy().replace('\\', "/"))
+}
+
+/// Resolves the Windows SDK include subfolders (`ucrt`, `shared`, `um`, `winrt`) for the
+/// latest installed SDK version, using the registry-recorded SDK root.
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - The SDK include subfolders
+/// * `Err(String)` - An error if the SDK root or version could not be determined
+fn find_windows_sdk_include_dirs() -> Result<Vec<String>, String> {
+    let sdk_root = find_windows_sdk_root()?;
+    let include_root = latest_subdirectory(&sdk_root.join("Include"))?;
+
+    Ok(["ucrt", "shared", "um", "winrt"]
+        .iter()
+        .map(|subfolder| include_root.join(subfolder).to_string_lossy().replace('\\', "/"))
+        .collect())
+}
+
+/// Reads the Windows 10/11 SDK installation folder from the registry via `reg query`.
+///
+/// # Returns
+///
+/// * `Ok(PathBuf)` - The SDK installation root
+/// * `Err(String)` - An error if the registry key could not be read
+fn find_windows_sdk_root() -> Result<PathBuf, String> {
+    let output = Command::new("reg")
+        .args([
+            "query",
+            r"HKLM\SOFTWARE\Wow6432Node\Microsoft\Microsoft SDKs\Windows\v10.0",
+            "/v",
+            "InstallationFolder",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to query Windows SDK registry key: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let folder = stdout
+        .lines()
+        .find_map(parse_reg_sz_value_line)
+        .ok_or_else(|| "Could not determine Windows SDK installation folder from registry".to_string())?;
+
+    Ok(PathBuf::from(folder.trim_end_matches('\\')))
+}
+
+/// Parses a `reg query` value line of the form `    <name>    REG_SZ    <value>`, returning
+/// `<value>` verbatim (including any internal spaces, e.g. `C:\Program Files (x86)\...`).
+///
+/// The value can't be found by splitting on whitespace, since it routinely contains spaces
+/// itself; instead this locates the `REG_SZ` type column and takes everything after the run
+/// of whitespace that follows it.
+///
+/// # Arguments
+///
+/// * `line` - A single line of `reg query` output
+///
+/// # Returns
+///
+/// The trimmed value, or `None` if the line doesn't contain a `REG_SZ` column.
+fn parse_reg_sz_value_line(line: &str) -> Option<String> {
+    let (_, after_type) = line.split_once("REG_SZ")?;
+    let value = after_type.trim_start().trim_end();
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
     }
 }
 
+/// Returns the subdirectory of `dir` with the numerically greatest dot-separated version
+/// name, used to pick the newest installed MSVC toolset or Windows SDK version.
+///
+/// # Arguments
+///
+/// * `dir` - Directory whose subdirectories are version folders
+///
+/// # Returns
+///
+/// * `Ok(PathBuf)` - The newest subdirectory
+/// * `Err(String)` - An error if `dir` has no subdirectories
+fn latest_subdirectory(dir: &Path) -> Result<PathBuf, String> {
+    let subdirs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+
+    subdirs
+        .into_iter()
+        .max_by_key(|path| path.file_name().and_then(|n| n.to_str()).map(version_key).unwrap_or_default())
+        .ok_or_else(|| format!("No subdirectories found under {}", dir.display()))
+}
+
+/// Parses a dot-separated version folder name (e.g. `14.29.30133`, `10.0.17763.0`) into a
+/// comparable key, so versions are ordered numerically rather than lexicographically — a
+/// plain string/path sort would put `"10.0.9600.0"` after `"10.0.17763.0"` since `'9' > '1'`.
+fn version_key(name: &str) -> Vec<u64> {
+    name.split('.').map(|segment| segment.parse().unwrap_or(0)).collect()
+}
+
 /// Extracts include directories by invoking a gcc-like compiler with verbose flags.
 ///
-/// Runs the compiler with `-v -E -x c++ -` arguments to generate verbose output
-/// about its configuration, then parses the stderr output to extract include directories.
+/// Runs the compiler with `[--target=<triple>] [<CFLAGS/CXXFLAGS>] -v -E -x <lang> [-std=<std>] -`
+/// arguments to generate verbose output about its configuration, then parses the stderr
+/// output to extract include directories.
 ///
 /// # Arguments
 ///
 /// * `compiler` - Path to the C++ compiler executable
+/// * `lang` - Source language to request via `-x`
+/// * `std` - Optional language standard forwarded to the compiler via `-std=`
+/// * `target` - Optional target triple, forwarded via `--target=` for Clang-like compilers
 ///
 /// # Returns
 ///
 /// * `Ok(Vec<String>)` - A vector of include directory paths
 /// * `Err(String)` - An error if the compiler fails to execute or no directories are found
-fn get_compiler_include_dirs(compiler: &PathBuf) -> Result<Vec<String>, String> {
+fn get_compiler_include_dirs(
+    compiler: &PathBuf,
+    lang: Lang,
+    std: Option<&str>,
+    target: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let mut command = Command::new(compiler);
+
+    // Clang selects a cross toolchain via --target=; cross-prefixed gcc binaries (resolved
+    // in resolve_compiler) don't need it.
+    if let Some(target) = target {
+        if is_clang_like_compiler(compiler) {
+            command.arg(format!("--target={}", target));
+        }
+    }
+
+    if let Ok(flags) = env::var(compiler_flags_env_var(lang)) {
+        command.args(flags.split_whitespace());
+    }
+
     // Run compiler with -v flag to get verbose output
-    // We need to provide some input, so we use echo with a simple C++ snippet
-    let output = Command::new(compiler)
-        .arg("-v")
-        .arg("-E")
-        .arg("-x")
-        .arg("c++")
+    // The input is `-` (stdin), which is left empty; only the -v output is needed
+    command.arg("-v").arg("-E").arg("-x").arg(lang.as_compiler_lang());
+    if let Some(std) = std {
+        command.arg(format!("-std={}", std));
+    }
+    // Force the C locale so the section markers we match below are stable
+    // regardless of the user's configured locale.
+    let output = command
         .arg("-")
+        .env("LC_ALL", "C")
+        .env("LANG", "C")
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -156,14 +508,22 @@ fn get_compiler_include_dirs(compiler: &PathBuf) -> Result<Vec<String>, String>
     // gcc-like compilers write -v output to stderr
     let stderr = String::from_utf8_lossy(&output.stderr);
 
-    parse_include_dirs(&stderr)
+    let dirs = parse_include_dirs(&stderr)?;
+    Ok(if cfg!(windows) {
+        fix_mingw_drive_relative_paths(dirs, compiler)
+    } else {
+        dirs
+    })
 }
 
 /// Parses include directories from gcc-like compiler verbose output.
 ///
-/// Extracts directory paths from the section between `#include <...> search starts here:`
-/// and `End of search list.` in the compiler's output. Also handles platform-specific
-/// annotations like `(framework directory)` on macOS.
+/// Extracts directory paths from the section opened by a line containing
+/// `#include <...>`. The full marker lines (`#include <...> search starts here:`,
+/// `End of search list.`) are localized by the compiler, so this only relies on the
+/// `#include <...>` substring to find the start, and on path lines being indented by
+/// exactly one space to find the extent of the section — any other line ends it.
+/// Also handles platform-specific annotations like `(framework directory)` on macOS.
 ///
 /// # Arguments
 ///
@@ -179,30 +539,29 @@ fn parse_include_dirs(compiler_output: &str) -> Result<Vec<String>, String> {
     let annotation_pattern = Regex::new(r"\s*\(.*\)$").unwrap();
 
     for line in compiler_output.lines() {
-        let trimmed = line.trim();
-
-        // Start of include directory section
-        if trimmed.contains("#include <...> search starts here:") {
-            in_include_section = true;
+        if !in_include_section {
+            // The opening line is localized except for this substring.
+            if line.contains("#include <...>") {
+                in_include_section = true;
+            }
             continue;
         }
 
-        // End of include directory section
-        if trimmed.contains("End of search list.") {
+        // Path lines are indented by exactly one space; anything else (including the
+        // localized "End of search list." line) ends the section.
+        let is_path_line = line.starts_with(' ') && !line.starts_with("  ");
+        if !is_path_line {
             break;
         }
 
-        // Collect directory paths
-        if in_include_section && !trimmed.is_empty() {
-            // Remove trailing annotations like "(framework directory)" on macOS
-            let cleaned = annotation_pattern.replace(trimmed, "");
-            let path = cleaned.trim();
+        // Remove trailing annotations like "(framework directory)" on macOS
+        let cleaned = annotation_pattern.replace(line.trim(), "");
+        let path = cleaned.trim();
 
-            if !path.is_empty() {
-                // Normalize path separators to forward slashes
-                let normalized = path.replace('\\', "/");
-                dirs.push(normalized);
-            }
+        if !path.is_empty() {
+            // Normalize path separators to forward slashes
+            let normalized = path.replace('\\', "/");
+            dirs.push(normalized);
         }
     }
 
@@ -212,3 +571,454 @@ fn parse_include_dirs(compiler_output: &str) -> Result<Vec<String>, String> {
         Ok(dirs)
     }
 }
+
+/// Gets compiler library search directories using the specified compiler or platform defaults.
+///
+/// # Arguments
+///
+/// * `compiler` - Optional path to a C++ compiler. Resolved the same way as for
+///   [`get_include_dirs`]: explicit path, then `CC`/`CXX`, then a target-prefixed cross
+///   compiler, then the platform default.
+/// * `lang` - Source language, used to pick between `CC` and `CXX`.
+/// * `target` - Optional target triple for cross-compilation.
+/// * `extra_args` - Additional compiler-style flags to scan for `-L`/`-Ldir` directories.
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - A vector of library search directory paths
+/// * `Err(String)` - An error message if the operation fails
+///
+/// # Platform behavior
+///
+/// - **Windows (no compiler, env var, or target given)**: Parses the `LIB` environment variable
+/// - **Unix-like (no compiler, env var, or target given)**: Uses `/usr/bin/c++`
+/// - **Compiler resolved**: Invokes the compiler with `-print-search-dirs`
+///
+/// Directories from the `LIBRARY_PATH` environment variable and from `extra_args` are
+/// folded in on top of whatever the compiler reports, then exact duplicates are removed
+/// (gcc-like compilers already fold `LIBRARY_PATH` into their own `-print-search-dirs`
+/// output, so it would otherwise show up twice).
+fn get_library_dirs(
+    compiler: Option<PathBuf>,
+    lang: Lang,
+    target: Option<&str>,
+    extra_args: &[String],
+) -> Result<Vec<String>, String> {
+    let mut dirs = if cfg!(windows) && !has_explicit_compiler(&compiler, lang, target) {
+        get_windows_library_dirs()?
+    } else {
+        let compiler_path = resolve_compiler(compiler, lang, target);
+
+        if cfg!(windows) && is_msvc_like_compiler(&compiler_path) {
+            get_windows_library_dirs()?
+        } else {
+            get_compiler_library_dirs(&compiler_path, target)?
+        }
+    };
+
+    if let Ok(library_path) = env::var("LIBRARY_PATH") {
+        dirs.extend(split_dir_list(&library_path));
+    }
+
+    dirs.extend(parse_library_search_args(extra_args));
+
+    let mut seen = std::collections::HashSet::new();
+    dirs.retain(|dir| seen.insert(dir.clone()));
+
+    Ok(dirs)
+}
+
+/// Extracts library directories from the Windows `LIB` environment variable.
+///
+/// Parses semicolon-separated paths from the `LIB` environment variable,
+/// filtering out empty entries.
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - A vector of library directory paths
+/// * `Err(String)` - An error if the `LIB` environment variable is not set
+fn get_windows_library_dirs() -> Result<Vec<String>, String> {
+    match env::var("LIB") {
+        Ok(lib_var) => {
+            let dirs: Vec<String> = lib_var
+                .split(';')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.replace('\\', "/"))
+                .collect();
+            Ok(dirs)
+        }
+        Err(_) => Err("LIB environment variable not set".to_string()),
+    }
+}
+
+/// Extracts library search directories by invoking a gcc-like compiler with `-print-search-dirs`.
+///
+/// # Arguments
+///
+/// * `compiler` - Path to the C++ compiler executable
+/// * `target` - Optional target triple, forwarded via `--target=` for Clang-like compilers
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - A vector of library directory paths (empty if the `libraries:` line lists none)
+/// * `Err(String)` - An error if the compiler fails to execute or its output has no `libraries:` line
+fn get_compiler_library_dirs(compiler: &PathBuf, target: Option<&str>) -> Result<Vec<String>, String> {
+    let mut command = Command::new(compiler);
+
+    // Clang selects a cross toolchain via --target=; cross-prefixed gcc binaries (resolved
+    // in resolve_compiler) don't need it.
+    if let Some(target) = target {
+        if is_clang_like_compiler(compiler) {
+            command.arg(format!("--target={}", target));
+        }
+    }
+
+    let output = command
+        .arg("-print-search-dirs")
+        .env("LC_ALL", "C")
+        .env("LANG", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output();
+
+    let output = output.map_err(|e| format!("Failed to execute compiler: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let dirs = parse_library_dirs(&stdout)?;
+    Ok(if cfg!(windows) {
+        fix_mingw_drive_relative_paths(dirs, compiler)
+    } else {
+        dirs
+    })
+}
+
+/// Parses the `libraries: =...` line from `cc -print-search-dirs` output.
+///
+/// # Arguments
+///
+/// * `compiler_output` - The stdout output from running the compiler with `-print-search-dirs`
+///
+/// # Returns
+///
+/// * `Ok(Vec<String>)` - A vector of library directory paths
+/// * `Err(String)` - An error if no `libraries:` line is found
+fn parse_library_dirs(compiler_output: &str) -> Result<Vec<String>, String> {
+    for line in compiler_output.lines() {
+        if let Some(rest) = line.trim().strip_prefix("libraries: =") {
+            return Ok(split_dir_list(rest));
+        }
+    }
+
+    Err("No library search directories found in compiler output".to_string())
+}
+
+/// Splits a directory list whose delimiter is ambiguous across platforms, as produced by
+/// `cc -print-search-dirs` (the `install:`/`libraries:` lines) and by environment variables
+/// such as `LIBRARY_PATH`.
+///
+/// The delimiter is detected with the following rule:
+///
+/// 1. If `;` appears anywhere in the string, it is the delimiter.
+/// 2. Otherwise, if the string looks like a single Windows path (an ASCII letter followed
+///    by `:` and a slash, e.g. `C:\...`), treat the whole string as one path.
+/// 3. Otherwise, split on `:`.
+///
+/// Each resulting entry is trimmed, empty entries are dropped, and backslashes are
+/// normalized to forward slashes (matching `parse_include_dirs`).
+///
+/// # Arguments
+///
+/// * `list` - The delimited directory list to split
+///
+/// # Returns
+///
+/// A vector of directory paths.
+fn split_dir_list(list: &str) -> Vec<String> {
+    let looks_like_windows_path = {
+        let bytes = list.as_bytes();
+        bytes.len() >= 3
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && (bytes[2] == b'/' || bytes[2] == b'\\')
+    };
+
+    let entries: Vec<&str> = if list.contains(';') {
+        list.split(';').collect()
+    } else if looks_like_windows_path {
+        vec![list]
+    } else {
+        list.split(':').collect()
+    };
+
+    entries
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.replace('\\', "/"))
+        .collect()
+}
+
+/// Fixes up MinGW GCC's drive-relative built-in search paths on Windows.
+///
+/// Some MinGW GCC builds emit absolute POSIX-style directories such as `/mingw/include`
+/// in their built-in search paths. GCC itself interprets these as relative to the
+/// *current drive*, so the effective path depends on the working directory. This
+/// reconstructs the path GCC actually resolves by prepending the drive of `compiler`
+/// (or, failing that, of the current working directory) to any directory that starts
+/// with `/` but has no drive letter.
+///
+/// # Arguments
+///
+/// * `dirs` - Directories as reported in compiler output
+/// * `compiler` - Path to the compiler executable, used to determine its drive
+///
+/// # Returns
+///
+/// The directories with ambiguous `/`-rooted paths resolved to an explicit drive.
+fn fix_mingw_drive_relative_paths(dirs: Vec<String>, compiler: &Path) -> Vec<String> {
+    let Some(drive) = mingw_drive_hint(compiler) else {
+        return dirs;
+    };
+
+    dirs.into_iter()
+        .map(|dir| {
+            if dir.starts_with('/') {
+                format!("{}{}", drive, dir)
+            } else {
+                dir
+            }
+        })
+        .collect()
+}
+
+/// Determines the drive letter (e.g. `C:`) to assume for drive-relative paths, preferring
+/// the compiler executable's own drive and falling back to the current working directory's.
+fn mingw_drive_hint(compiler: &Path) -> Option<String> {
+    compiler
+        .canonicalize()
+        .ok()
+        .as_deref()
+        .and_then(drive_letter)
+        .or_else(|| env::current_dir().ok().as_deref().and_then(drive_letter))
+}
+
+/// Returns the drive letter prefix (e.g. `C:`) of `path`, if it has one.
+fn drive_letter(path: &Path) -> Option<String> {
+    let s = path.to_str()?;
+    // `canonicalize` on Windows yields verbatim paths such as `\\?\C:\...`.
+    let s = s.strip_prefix(r"\\?\").unwrap_or(s);
+    let bytes = s.as_bytes();
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        Some(s[..2].to_string())
+    } else {
+        None
+    }
+}
+
+/// Extracts directories from `-L <dir>` / `-Ldir` style compiler flags.
+///
+/// Recognizes both the split (`-L dir`) and joined (`-Ldir`) forms, skips relative
+/// paths, and returns each match normalized to an absolute path.
+///
+/// # Arguments
+///
+/// * `tokens` - A list of compiler-style flag tokens to scan
+///
+/// # Returns
+///
+/// A vector of absolute library directory paths found among `tokens`.
+fn parse_library_search_args(tokens: &[String]) -> Vec<String> {
+    let mut dirs = Vec::new();
+    let mut iter = tokens.iter();
+
+    while let Some(token) = iter.next() {
+        let dir = if token == "-L" {
+            iter.next().cloned()
+        } else {
+            token.strip_prefix("-L").filter(|rest| !rest.is_empty()).map(str::to_string)
+        };
+
+        if let Some(dir) = dir {
+            let path = PathBuf::from(&dir);
+            if path.is_absolute() {
+                dirs.push(path.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_include_dirs_extracts_plain_english_gcc_output() {
+        let output = "\
+#include <...> search starts here:
+ /usr/include/c++/12
+ /usr/include/x86_64-linux-gnu/c++/12
+ /usr/local/include
+End of search list.
+";
+
+        assert_eq!(
+            parse_include_dirs(output).unwrap(),
+            vec!["/usr/include/c++/12", "/usr/include/x86_64-linux-gnu/c++/12", "/usr/local/include"]
+        );
+    }
+
+    #[test]
+    fn parse_include_dirs_extracts_non_english_locale_output() {
+        // The opening/closing banner lines are localized (here, German), but the
+        // "#include <...>" substring and the one-space indentation of path lines
+        // are locale-independent, which is what the parser actually relies on.
+        let output = "\
+#include <...> Suche beginnt hier:
+ /usr/include/c++/12
+ /usr/local/include
+Ende der Suchliste.
+";
+
+        assert_eq!(parse_include_dirs(output).unwrap(), vec!["/usr/include/c++/12", "/usr/local/include"]);
+    }
+
+    #[test]
+    fn parse_include_dirs_strips_macos_framework_directory_annotation() {
+        let output = "\
+#include <...> search starts here:
+ /usr/local/include
+ /System/Library/Frameworks (framework directory)
+End of search list.
+";
+
+        assert_eq!(
+            parse_include_dirs(output).unwrap(),
+            vec!["/usr/local/include", "/System/Library/Frameworks"]
+        );
+    }
+
+    #[test]
+    fn parse_include_dirs_errors_when_section_missing() {
+        assert!(parse_include_dirs("no relevant output here\n").is_err());
+    }
+
+    #[test]
+    fn parse_library_search_args_recognizes_split_and_joined_forms() {
+        let tokens = vec!["-L".to_string(), "/usr/lib64".to_string(), "-L/opt/lib".to_string()];
+
+        assert_eq!(parse_library_search_args(&tokens), vec!["/usr/lib64", "/opt/lib"]);
+    }
+
+    #[test]
+    fn parse_library_search_args_skips_relative_paths() {
+        let tokens = vec!["-Lrelative/lib".to_string(), "-L".to_string(), "../lib".to_string()];
+
+        assert!(parse_library_search_args(&tokens).is_empty());
+    }
+
+    #[test]
+    fn parse_library_search_args_ignores_bare_dash_l_at_end_of_tokens() {
+        let tokens = vec!["-L".to_string()];
+
+        assert!(parse_library_search_args(&tokens).is_empty());
+    }
+
+    #[test]
+    fn parse_library_search_args_ignores_unrelated_flags() {
+        let tokens = vec!["-O2".to_string(), "-DFOO".to_string()];
+
+        assert!(parse_library_search_args(&tokens).is_empty());
+    }
+
+    #[test]
+    fn parse_library_dirs_extracts_libraries_line() {
+        let output = "install: /usr/lib/gcc/x86_64-linux-gnu/12/\nlibraries: =/usr/lib:/usr/lib64\n";
+
+        assert_eq!(parse_library_dirs(output).unwrap(), vec!["/usr/lib", "/usr/lib64"]);
+    }
+
+    #[test]
+    fn parse_library_dirs_errors_when_libraries_line_missing() {
+        let output = "install: /usr/lib/gcc/x86_64-linux-gnu/12/\n";
+
+        assert!(parse_library_dirs(output).is_err());
+    }
+
+    #[test]
+    fn split_dir_list_splits_on_semicolon_when_present() {
+        assert_eq!(split_dir_list(r"C:\mingw\lib;C:\mingw\include"), vec!["C:/mingw/lib", "C:/mingw/include"]);
+    }
+
+    #[test]
+    fn split_dir_list_treats_single_windows_path_as_one_entry() {
+        assert_eq!(split_dir_list(r"C:\Program Files\mingw\lib"), vec!["C:/Program Files/mingw/lib"]);
+    }
+
+    #[test]
+    fn split_dir_list_splits_on_colon_otherwise() {
+        assert_eq!(split_dir_list("/usr/lib:/usr/lib64"), vec!["/usr/lib", "/usr/lib64"]);
+    }
+
+    #[test]
+    fn split_dir_list_drops_empty_entries_and_trims_whitespace() {
+        assert_eq!(split_dir_list("/usr/lib: :/usr/lib64:"), vec!["/usr/lib", "/usr/lib64"]);
+    }
+
+    #[test]
+    fn drive_letter_extracts_prefix_from_windows_path() {
+        assert_eq!(drive_letter(Path::new(r"C:\mingw64\bin")), Some("C:".to_string()));
+    }
+
+    #[test]
+    fn drive_letter_returns_none_for_posix_path() {
+        assert_eq!(drive_letter(Path::new("/mingw64/bin")), None);
+    }
+
+    #[test]
+    fn fix_mingw_drive_relative_paths_leaves_rooted_paths_untouched_without_a_drive_hint() {
+        // Neither the POSIX compiler path (or its canonical form) nor this test process's own working
+        // directory carries a Windows drive letter, so no drive hint is available and
+        // rooted paths must pass through unchanged.
+        let dirs = vec!["/mingw64/include".to_string(), "relative/include".to_string()];
+
+        assert_eq!(fix_mingw_drive_relative_paths(dirs.clone(), Path::new("/usr/bin/gcc")), dirs);
+    }
+
+    #[test]
+    fn parse_reg_sz_value_line_preserves_internal_spaces() {
+        let line = r"    InstallationFolder    REG_SZ    C:\Program Files (x86)\Windows Kits\10\";
+
+        assert_eq!(
+            parse_reg_sz_value_line(line).as_deref(),
+            Some(r"C:\Program Files (x86)\Windows Kits\10\")
+        );
+    }
+
+    #[test]
+    fn parse_reg_sz_value_line_returns_none_without_a_reg_sz_column() {
+        assert_eq!(parse_reg_sz_value_line("    Name    REG_DWORD    0x1"), None);
+    }
+
+    #[test]
+    fn version_key_orders_numerically_not_lexicographically() {
+        assert!(version_key("10.0.17763.0") > version_key("10.0.9600.0"));
+        assert!(version_key("14.29.30133") > version_key("14.9.0"));
+    }
+
+    #[test]
+    fn latest_subdirectory_picks_numerically_greatest_version_folder() {
+        let root = env::temp_dir().join(format!("get-system-include-dirs-test-{:?}", std::thread::current().id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for name in ["10.0.9600.0", "10.0.17763.0", "10.0.10240.0"] {
+            std::fs::create_dir_all(root.join(name)).unwrap();
+        }
+
+        let result = latest_subdirectory(&root).unwrap();
+
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(result.file_name().unwrap().to_str().unwrap(), "10.0.17763.0");
+    }
+}